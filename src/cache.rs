@@ -0,0 +1,117 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::hash::HashType;
+
+/// 以 `路径 -> (体积, 修改时间, 哈希算法, 读取字节数上限, 哈希)` 的形式缓存完整哈希
+/// 结果，重复比较同一棵基本没变化的目录树时可以跳过绝大多数文件的实际读取。
+/// 哈希算法和 `--max-read-bytes` 也记在条目里：换一种算法或改变读取上限会
+/// 算出完全不同的哈希，不能直接复用旧条目。
+///
+/// 注意覆盖范围：只有在大小、分块哈希两阶段都撞组、真正进入第三阶段的文件
+/// 才会查/写这份缓存。在“基本没变化的目录树”这个典型场景下，绝大多数文件
+/// 在按大小分组时就已经被判定为唯一，根本不会走到这里——缓存省下的是这一
+/// 小撮“仍然可能重复”的文件重新计算完整哈希的开销，而不是整棵树的遍历、
+/// 分块哈希等工作，所以实际加速幅度取决于树里真正重复的文件占比。
+#[derive(Serialize, Deserialize, Clone)]
+struct CacheEntry {
+    size: u64,
+    modified: SystemTime,
+    hash_type: HashType,
+    max_read_bytes: Option<u64>,
+    hash: String,
+}
+
+pub struct HashCache {
+    path: Option<PathBuf>,
+    entries: HashMap<String, CacheEntry>,
+    dirty: bool,
+}
+
+impl HashCache {
+    /// 默认缓存文件放在系统缓存目录下，找不到时退化为不持久化。
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("folder_compare").join("hash_cache.json"))
+    }
+
+    pub fn load(path: Option<PathBuf>) -> Result<Self> {
+        let path = path.or_else(Self::default_path);
+        let entries = match &path {
+            Some(p) if p.exists() => {
+                let data = fs::read_to_string(p)
+                    .with_context(|| format!("无法读取缓存文件: {}", p.display()))?;
+                serde_json::from_str(&data).unwrap_or_default()
+            }
+            _ => HashMap::new(),
+        };
+        Ok(Self {
+            path,
+            entries,
+            dirty: false,
+        })
+    }
+
+    pub fn get(
+        &self,
+        path: &Path,
+        size: u64,
+        modified: SystemTime,
+        hash_type: HashType,
+        max_read_bytes: Option<u64>,
+    ) -> Option<String> {
+        let key = path.to_string_lossy().into_owned();
+        self.entries
+            .get(&key)
+            .filter(|entry| {
+                entry.size == size
+                    && entry.modified == modified
+                    && entry.hash_type == hash_type
+                    && entry.max_read_bytes == max_read_bytes
+            })
+            .map(|entry| entry.hash.clone())
+    }
+
+    pub fn insert(
+        &mut self,
+        path: &Path,
+        size: u64,
+        modified: SystemTime,
+        hash_type: HashType,
+        max_read_bytes: Option<u64>,
+        hash: String,
+    ) {
+        let key = path.to_string_lossy().into_owned();
+        self.entries.insert(
+            key,
+            CacheEntry {
+                size,
+                modified,
+                hash_type,
+                max_read_bytes,
+                hash,
+            },
+        );
+        self.dirty = true;
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("无法创建缓存目录: {}", parent.display()))?;
+        }
+        let data = serde_json::to_string_pretty(&self.entries)
+            .with_context(|| "无法序列化哈希缓存".to_string())?;
+        fs::write(path, data).with_context(|| format!("无法写入缓存文件: {}", path.display()))?;
+        Ok(())
+    }
+}
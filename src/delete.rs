@@ -0,0 +1,120 @@
+use clap::ValueEnum;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use crate::Origin;
+
+/// 同一哈希下该保留哪些文件、删除哪些文件的策略，基于各文件的修改时间而不是
+/// 简单地把 A 当作权威来源、删光 B 里的重复项。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DeleteMethod {
+    /// 只保留修改时间最新的一份，其余全部删除
+    AllExceptNewest,
+    /// 只保留修改时间最旧的一份，其余全部删除
+    AllExceptOldest,
+    /// 只删除修改时间最旧的一份
+    OneOldest,
+    /// 只删除修改时间最新的一份
+    OneNewest,
+    /// 不删除任何文件
+    None,
+}
+
+impl Default for DeleteMethod {
+    fn default() -> Self {
+        DeleteMethod::None
+    }
+}
+
+/// 一个具有相同内容哈希的文件及其修改时间、（在 Unix 上）所在设备与 inode。
+#[derive(Debug, Clone)]
+pub struct DupeFile {
+    pub path: PathBuf,
+    pub modified: SystemTime,
+    /// `(设备号, inode 号)`，仅 Unix 上可用，用于识别已经互为硬链接的文件。
+    pub inode: Option<(u64, u64)>,
+    /// 文件来自 A 还是 B 目录的扫描，在扫描时打好标签传过来，而不是之后
+    /// 再用路径前缀猜——嵌套或相同的 A/B 目录会让前缀判断出错。
+    pub origin: Origin,
+}
+
+/// 共享同一个哈希的一组文件，可能横跨 A、B 两个目录。
+#[derive(Debug, Clone)]
+pub struct DuplicateGroup {
+    pub hash: String,
+    pub files: Vec<DupeFile>,
+}
+
+impl DuplicateGroup {
+    /// 按照策略选出保留的“幸存文件”以及其余应当被处理（删除/替换为链接）的文件。
+    pub fn survivor_and_removals(&self, method: DeleteMethod) -> Option<(PathBuf, Vec<PathBuf>)> {
+        if method == DeleteMethod::None || self.files.len() < 2 {
+            return None;
+        }
+
+        let mut sorted = self.files.clone();
+        sorted.sort_by_key(|f| f.modified);
+
+        let (keep, removed) = match method {
+            DeleteMethod::None => unreachable!(),
+            DeleteMethod::AllExceptNewest => (
+                sorted.last().unwrap().path.clone(),
+                sorted[..sorted.len() - 1]
+                    .iter()
+                    .map(|f| f.path.clone())
+                    .collect(),
+            ),
+            DeleteMethod::AllExceptOldest => (
+                sorted[0].path.clone(),
+                sorted[1..].iter().map(|f| f.path.clone()).collect(),
+            ),
+            DeleteMethod::OneOldest => (
+                sorted.last().unwrap().path.clone(),
+                vec![sorted[0].path.clone()],
+            ),
+            DeleteMethod::OneNewest => (
+                sorted[0].path.clone(),
+                vec![sorted[sorted.len() - 1].path.clone()],
+            ),
+        };
+
+        Some((keep, removed))
+    }
+
+    /// 链接动作（`l`/`s`）专用的幸存文件选择：删除需要显式策略才执行，以免
+    /// 误删文件，但链接只是把重复文件换成指向幸存文件的链接，不会丢数据，
+    /// 不应该因为用户没传 `--delete-method` 就整组跳过。没有显式策略时默认
+    /// 保留组内第一份（`files` 里 A 目录的文件排在 B 之前，即 A 优先）。
+    pub fn survivor_and_victims_for_link(&self, method: DeleteMethod) -> Option<(PathBuf, Vec<PathBuf>)> {
+        if method != DeleteMethod::None {
+            return self.survivor_and_removals(method);
+        }
+        if self.files.len() < 2 {
+            return None;
+        }
+        let keep = self.files[0].path.clone();
+        let removed = self.files[1..].iter().map(|f| f.path.clone()).collect();
+        Some((keep, removed))
+    }
+
+    /// 按照删除策略，返回这一组里应当被删除的文件路径。
+    pub fn files_to_delete(&self, method: DeleteMethod) -> Vec<PathBuf> {
+        self.survivor_and_removals(method)
+            .map(|(_, removed)| removed)
+            .unwrap_or_default()
+    }
+
+    /// 组内已经互为硬链接（设备号、inode 号都相同）的文件数量，这些不是“可回收”的重复。
+    pub fn already_hardlinked_count(&self) -> usize {
+        let mut seen = std::collections::HashSet::new();
+        let mut already_linked = 0;
+        for file in &self.files {
+            if let Some(inode) = file.inode {
+                if !seen.insert(inode) {
+                    already_linked += 1;
+                }
+            }
+        }
+        already_linked
+    }
+}
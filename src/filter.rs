@@ -0,0 +1,119 @@
+use anyhow::{Context, Result};
+use glob::Pattern;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// 一个文件在扫描阶段被跳过的原因，用于汇总统计。
+pub enum SkipReason {
+    Extension,
+    MinSize,
+    ExcludePath,
+}
+
+/// 按扩展名白名单/黑名单、最小体积、路径通配符跳过的文件计数，随最终结果一起展示。
+#[derive(Debug, Default)]
+pub struct FilterStats {
+    pub skipped_ext: usize,
+    pub skipped_size: usize,
+    pub skipped_path: usize,
+}
+
+impl FilterStats {
+    pub fn record(&mut self, reason: SkipReason) {
+        match reason {
+            SkipReason::Extension => self.skipped_ext += 1,
+            SkipReason::MinSize => self.skipped_size += 1,
+            SkipReason::ExcludePath => self.skipped_path += 1,
+        }
+    }
+
+    pub fn merge(&mut self, other: &FilterStats) {
+        self.skipped_ext += other.skipped_ext;
+        self.skipped_size += other.skipped_size;
+        self.skipped_path += other.skipped_path;
+    }
+
+    pub fn total(&self) -> usize {
+        self.skipped_ext + self.skipped_size + self.skipped_path
+    }
+}
+
+/// 扫描阶段要应用的过滤条件。
+pub struct FileFilter {
+    allowed_ext: Option<HashSet<String>>,
+    excluded_ext: HashSet<String>,
+    min_size: Option<u64>,
+    exclude_globs: Vec<Pattern>,
+}
+
+fn normalize_ext(ext: &str) -> String {
+    ext.trim().trim_start_matches('.').to_lowercase()
+}
+
+fn file_ext(path: &Path) -> Option<String> {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+}
+
+impl FileFilter {
+    pub fn new(
+        ext: Option<&[String]>,
+        exclude_ext: Option<&[String]>,
+        min_size: Option<u64>,
+        exclude_path: Option<&[String]>,
+    ) -> Result<Self> {
+        let allowed_ext = ext.map(|list| list.iter().map(|e| normalize_ext(e)).collect());
+        let excluded_ext = exclude_ext
+            .unwrap_or(&[])
+            .iter()
+            .map(|e| normalize_ext(e))
+            .collect();
+        let exclude_globs = exclude_path
+            .unwrap_or(&[])
+            .iter()
+            .map(|pattern| {
+                Pattern::new(pattern).with_context(|| format!("无效的 glob 模式: {}", pattern))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            allowed_ext,
+            excluded_ext,
+            min_size,
+            exclude_globs,
+        })
+    }
+
+    /// 返回 `None` 表示该文件应当保留参与比较，`Some(reason)` 表示应跳过。
+    pub fn skip_reason(&self, path: &Path, size: u64) -> Option<SkipReason> {
+        if self
+            .exclude_globs
+            .iter()
+            .any(|pattern| pattern.matches_path(path))
+        {
+            return Some(SkipReason::ExcludePath);
+        }
+
+        let ext = file_ext(path);
+        if let Some(ext) = &ext {
+            if self.excluded_ext.contains(ext) {
+                return Some(SkipReason::Extension);
+            }
+        }
+        if let Some(allowed) = &self.allowed_ext {
+            let keep = ext.as_ref().map(|e| allowed.contains(e)).unwrap_or(false);
+            if !keep {
+                return Some(SkipReason::Extension);
+            }
+        }
+
+        if let Some(min_size) = self.min_size {
+            if size < min_size {
+                return Some(SkipReason::MinSize);
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,82 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// 可选的哈希算法。xxh3/crc32 面向“内容是否相同”的场景，吞吐量远高于
+/// md5，默认选 xxh3；blake3 用于需要更强抗碰撞性的场景；md5 保留用于
+/// 兼容旧的比较结果。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+pub enum HashType {
+    Blake3,
+    Xxh3,
+    Crc32,
+    Md5,
+}
+
+impl Default for HashType {
+    fn default() -> Self {
+        HashType::Xxh3
+    }
+}
+
+/// 统一的增量哈希接口，屏蔽各个哈希库自身的 API 差异。
+pub trait MyHasher {
+    fn update(&mut self, data: &[u8]);
+    fn finalize(&self) -> String;
+}
+
+struct Blake3Hasher(blake3::Hasher);
+
+impl MyHasher for Blake3Hasher {
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finalize(&self) -> String {
+        self.0.finalize().to_hex().to_string()
+    }
+}
+
+struct Xxh3Hasher(xxhash_rust::xxh3::Xxh3);
+
+impl MyHasher for Xxh3Hasher {
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finalize(&self) -> String {
+        format!("{:016x}", self.0.digest())
+    }
+}
+
+struct Crc32Hasher(crc32fast::Hasher);
+
+impl MyHasher for Crc32Hasher {
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finalize(&self) -> String {
+        format!("{:08x}", self.0.clone().finalize())
+    }
+}
+
+struct Md5Hasher(md5::Context);
+
+impl MyHasher for Md5Hasher {
+    fn update(&mut self, data: &[u8]) {
+        self.0.consume(data);
+    }
+
+    fn finalize(&self) -> String {
+        format!("{:x}", self.0.clone().compute())
+    }
+}
+
+pub fn new_hasher(hash_type: HashType) -> Box<dyn MyHasher> {
+    match hash_type {
+        HashType::Blake3 => Box::new(Blake3Hasher(blake3::Hasher::new())),
+        HashType::Xxh3 => Box::new(Xxh3Hasher(xxhash_rust::xxh3::Xxh3::new())),
+        HashType::Crc32 => Box::new(Crc32Hasher(crc32fast::Hasher::new())),
+        HashType::Md5 => Box::new(Md5Hasher(md5::Context::new())),
+    }
+}
@@ -0,0 +1,57 @@
+use anyhow::{Context, Result};
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 比较完成后的 `l` 操作（硬链接）还是 `s` 操作（符号链接）；没有单独的
+/// `--link`/`--symlink` 命令行参数。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkKind {
+    Hard,
+    Symbolic,
+}
+
+/// 把 `victim` 替换成指向 `target` 的链接：先在同目录下建一个临时名字的链接，
+/// 成功后再原子地 rename 到 `victim`，这样任何一步失败都不会丢失原文件内容。
+pub fn replace_with_link(victim: &Path, target: &Path, kind: LinkKind) -> Result<()> {
+    let tmp_path = temp_sibling_path(victim);
+
+    match kind {
+        LinkKind::Hard => fs::hard_link(target, &tmp_path)
+            .with_context(|| format!("无法创建硬链接: {}", tmp_path.display()))?,
+        LinkKind::Symbolic => {
+            // 符号链接存的是字面路径，按“相对于链接自身所在目录”解析。如果
+            // target 是相对路径（命令行传入的相对目录参数就是这样），直接存
+            // 进链接会在 victim 所在目录下解析出完全不同的位置，变成悬空
+            // 链接。这里先把 target 转成绝对路径，保证无论 victim 在哪都能
+            // 正确解析。
+            let absolute_target = fs::canonicalize(target).with_context(|| {
+                format!("无法解析链接目标的绝对路径: {}", target.display())
+            })?;
+            create_symlink(&absolute_target, &tmp_path)
+                .with_context(|| format!("无法创建符号链接: {}", tmp_path.display()))?
+        }
+    }
+
+    fs::rename(&tmp_path, victim)
+        .with_context(|| format!("无法用链接替换: {}", victim.display()))?;
+
+    Ok(())
+}
+
+fn temp_sibling_path(path: &Path) -> PathBuf {
+    let mut tmp_name = OsString::from(".");
+    tmp_name.push(path.file_name().unwrap_or_default());
+    tmp_name.push(".folder_compare_tmp");
+    path.with_file_name(tmp_name)
+}
+
+#[cfg(unix)]
+fn create_symlink(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn create_symlink(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_file(target, link)
+}
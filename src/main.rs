@@ -1,37 +1,273 @@
+mod cache;
+mod delete;
+mod filter;
+mod hash;
+mod link;
+mod report;
+
 use anyhow::{Context, Result};
+use cache::HashCache;
+use clap::Parser;
 use colored::*;
+use delete::{DeleteMethod, DupeFile, DuplicateGroup};
+use filter::{FileFilter, FilterStats};
+use hash::HashType;
 use indicatif::{ProgressBar, ProgressStyle};
-use md5;
+use link::LinkKind;
 use rayon::prelude::*;
+use report::ReportFormat;
 use std::{
     collections::HashMap,
-    env,
     fs::{self, File},
-    io::{Read, Write},
+    io::{BufReader, Read, Write},
     path::{Path, PathBuf},
-    process::exit, 
     sync::{Arc, Mutex},
 };
 use walkdir::WalkDir;
 
-fn calculate_md5(path: &Path) -> Result<String> {
+/// 分块哈希只读取文件开头这么多字节，足以把内容明显不同的文件提前排除。
+const PARTIAL_HASH_BYTES: usize = 8192;
+
+/// 流式读取完整文件时使用的缓冲区大小，避免一次性把整个文件读进内存。
+const STREAM_BUFFER_BYTES: usize = 64 * 1024;
+
+#[derive(Parser)]
+#[command(name = "folder_compare", about = "比较两个文件夹中的文件，找出重复与独有内容")]
+struct Cli {
+    /// 源目录 A
+    dir_a: PathBuf,
+    /// 目标目录 B
+    dir_b: PathBuf,
+    /// 比较完成后执行的操作：y 删除重复，o 输出重复列表，u 输出独有列表，l/s 替换为硬/符号链接
+    action: Option<String>,
+    /// 使用的哈希算法
+    #[arg(long, value_enum, default_value_t = HashType::Xxh3)]
+    hash: HashType,
+    /// 计算完整哈希时最多读取的字节数，超出部分不参与哈希（不设置则读取整个文件）
+    #[arg(long)]
+    max_read_bytes: Option<u64>,
+    /// 执行 [y]/[l]/[s] 时，同一哈希的一组文件里保留/处理哪些，按修改时间判断
+    #[arg(long, value_enum, default_value_t = DeleteMethod::None)]
+    delete_method: DeleteMethod,
+    /// 只比较这些扩展名的文件（逗号分隔，如 jpg,png）
+    #[arg(long, value_delimiter = ',')]
+    ext: Option<Vec<String>>,
+    /// 排除这些扩展名的文件（逗号分隔）
+    #[arg(long, value_delimiter = ',')]
+    exclude_ext: Option<Vec<String>>,
+    /// 忽略小于该体积（字节）的文件
+    #[arg(long)]
+    min_size: Option<u64>,
+    /// 排除匹配这些 glob 模式的路径（逗号分隔，如 **/node_modules/**,**/.git/**）
+    #[arg(long, value_delimiter = ',')]
+    exclude_path: Option<Vec<String>>,
+    /// 不使用哈希缓存，每次都重新计算完整哈希
+    #[arg(long)]
+    no_cache: bool,
+    /// 哈希缓存文件路径（不设置则使用系统缓存目录）
+    #[arg(long)]
+    cache_file: Option<PathBuf>,
+    /// 机器可读报告的输出格式
+    #[arg(long, value_enum, default_value_t = ReportFormat::Text)]
+    format: ReportFormat,
+    /// 把报告写入该文件，而不是打印到终端
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+fn calculate_hash(path: &Path, hash_type: HashType, max_read_bytes: Option<u64>) -> Result<String> {
+    let file = File::open(path)
+        .with_context(|| format!("无法打开文件: {}", path.display()))?;
+    let mut reader = BufReader::with_capacity(STREAM_BUFFER_BYTES, file);
+    let mut buffer = [0u8; STREAM_BUFFER_BYTES];
+    let mut hasher = hash::new_hasher(hash_type);
+    let mut read_so_far: u64 = 0;
+
+    loop {
+        let remaining = max_read_bytes.map(|limit| limit.saturating_sub(read_so_far));
+        if remaining == Some(0) {
+            break;
+        }
+        let want = remaining
+            .map(|r| std::cmp::min(r, buffer.len() as u64) as usize)
+            .unwrap_or(buffer.len());
+        let n = reader
+            .read(&mut buffer[..want])
+            .with_context(|| format!("无法读取文件: {}", path.display()))?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+        read_so_far += n as u64;
+    }
+
+    Ok(hasher.finalize())
+}
+
+fn calculate_partial_hash(
+    path: &Path,
+    hash_type: HashType,
+    max_read_bytes: Option<u64>,
+) -> Result<String> {
+    // 分块哈希只是预筛选，不能比完整哈希读得更多：如果 `--max-read-bytes`
+    // 比 PARTIAL_HASH_BYTES 还小，两个文件可能在上限内完全一致、只在上限和
+    // 8 KiB 之间有差异，这时分块哈希必须按同样的上限截断，否则会被分块哈希
+    // 提前错误地判定为不重复，根本不会进入完整哈希阶段比较。
+    let partial_bytes = max_read_bytes
+        .map(|limit| std::cmp::min(limit, PARTIAL_HASH_BYTES as u64) as usize)
+        .unwrap_or(PARTIAL_HASH_BYTES);
     let mut file = File::open(path)
         .with_context(|| format!("无法打开文件: {}", path.display()))?;
-    let mut buffer = Vec::new();
-    file.read_to_end(&mut buffer)
-        .with_context(|| format!("无法读取文件: {}", path.display()))?;
-    let digest = md5::compute(&buffer);
-    Ok(format!("{:x}", digest))
+    let mut buffer = vec![0u8; partial_bytes];
+    let mut read_total = 0;
+    loop {
+        let n = file
+            .read(&mut buffer[read_total..])
+            .with_context(|| format!("无法读取文件: {}", path.display()))?;
+        if n == 0 {
+            break;
+        }
+        read_total += n;
+        if read_total == buffer.len() {
+            break;
+        }
+    }
+    let mut hasher = hash::new_hasher(hash_type);
+    hasher.update(&buffer[..read_total]);
+    Ok(hasher.finalize())
 }
 
-fn get_md5_dict(dir: &Path) -> Result<HashMap<String, Vec<PathBuf>>> {
-    let paths: Vec<_> = WalkDir::new(dir)
+fn collect_files(dir: &Path, filter: &FileFilter) -> (Vec<PathBuf>, FilterStats) {
+    let mut files = Vec::new();
+    let mut stats = FilterStats::default();
+
+    for entry in WalkDir::new(dir)
         .into_iter()
         .filter_map(Result::ok)
         .filter(|e| e.file_type().is_file())
-        .map(|e| e.into_path())
-        .collect();
+    {
+        let path = entry.into_path();
+        let size = match fs::metadata(&path) {
+            Ok(metadata) => metadata.len(),
+            Err(_) => continue,
+        };
+        match filter.skip_reason(&path, size) {
+            Some(reason) => stats.record(reason),
+            None => files.push(path),
+        }
+    }
+
+    (files, stats)
+}
+
+/// 标记一个路径来自 A 还是 B 目录的扫描，避免后续只能靠 `starts_with` 猜测
+/// 归属——当 B 嵌套在 A 内部（或两个参数相同）时，路径前缀判断会出错。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Origin {
+    A,
+    B,
+}
 
+#[cfg(unix)]
+fn file_inode(metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_inode(_metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+fn group_by_size(paths: &[(PathBuf, Origin)]) -> HashMap<u64, Vec<(PathBuf, Origin)>> {
+    let mut groups: HashMap<u64, Vec<(PathBuf, Origin)>> = HashMap::new();
+    for (path, origin) in paths {
+        if let Ok(metadata) = fs::metadata(path) {
+            groups
+                .entry(metadata.len())
+                .or_default()
+                .push((path.clone(), *origin));
+        }
+    }
+    groups
+}
+
+/// 只保留组内文件数 > 1 的分组，单独一个文件的分组必然是唯一文件，没必要再读它的内容。
+fn candidates_from_groups<K: Eq + std::hash::Hash, T: Clone>(
+    groups: HashMap<K, Vec<T>>,
+) -> (Vec<T>, Vec<T>) {
+    let mut candidates = Vec::new();
+    let mut unique = Vec::new();
+    for (_, items) in groups {
+        if items.len() > 1 {
+            candidates.extend(items);
+        } else {
+            unique.extend(items);
+        }
+    }
+    (candidates, unique)
+}
+
+fn group_by_partial_hash(
+    paths: &[(PathBuf, Origin)],
+    hash_type: HashType,
+    max_read_bytes: Option<u64>,
+) -> HashMap<String, Vec<(PathBuf, Origin)>> {
+    let map = Arc::new(Mutex::new(HashMap::<String, Vec<(PathBuf, Origin)>>::new()));
+    paths.par_iter().for_each(|(path, origin)| {
+        match calculate_partial_hash(path, hash_type, max_read_bytes) {
+            Ok(hash) => {
+                let mut map_lock = map.lock().unwrap();
+                map_lock.entry(hash).or_default().push((path.clone(), *origin));
+            }
+            Err(e) => eprintln!("计算文件 {} 分块哈希时出错: {}", path.display(), e),
+        }
+    });
+    Arc::try_unwrap(map).unwrap().into_inner().unwrap()
+}
+
+/// 先查缓存，命中且体积、修改时间都一致时跳过实际读取，否则计算后写回缓存。
+fn hash_with_cache(
+    path: &Path,
+    hash_type: HashType,
+    max_read_bytes: Option<u64>,
+    cache: Option<&Mutex<HashCache>>,
+) -> Result<String> {
+    let metadata =
+        fs::metadata(path).with_context(|| format!("无法读取元数据: {}", path.display()))?;
+    let size = metadata.len();
+    let modified = metadata
+        .modified()
+        .with_context(|| format!("无法读取修改时间: {}", path.display()))?;
+
+    if let Some(cache) = cache {
+        if let Some(hash) = cache
+            .lock()
+            .unwrap()
+            .get(path, size, modified, hash_type, max_read_bytes)
+        {
+            return Ok(hash);
+        }
+    }
+
+    let hash = calculate_hash(path, hash_type, max_read_bytes)?;
+
+    if let Some(cache) = cache {
+        cache
+            .lock()
+            .unwrap()
+            .insert(path, size, modified, hash_type, max_read_bytes, hash.clone());
+    }
+
+    Ok(hash)
+}
+
+fn get_hash_dict(
+    paths: &[PathBuf],
+    hash_type: HashType,
+    max_read_bytes: Option<u64>,
+    cache: Option<&Mutex<HashCache>>,
+) -> Result<HashMap<String, Vec<PathBuf>>> {
     let total = paths.len() as u64;
     let pb = ProgressBar::new(total);
     pb.set_style(
@@ -40,87 +276,223 @@ fn get_md5_dict(dir: &Path) -> Result<HashMap<String, Vec<PathBuf>>> {
             .unwrap()
             .progress_chars("=>-"),
     );
-    pb.set_message(format!("计算 {}", dir.display()));
+    pb.set_message("计算完整哈希".to_string());
 
     let map = Arc::new(Mutex::new(HashMap::<String, Vec<PathBuf>>::new()));
-    
+
     paths.par_iter().for_each(|path| {
-        match calculate_md5(path) {
+        match hash_with_cache(path, hash_type, max_read_bytes, cache) {
             Ok(hash) => {
                 let mut map_lock = map.lock().unwrap();
                 map_lock.entry(hash).or_default().push(path.clone());
             }
-            Err(e) => eprintln!("计算文件 {} MD5 时出错: {}", path.display(), e),
+            Err(e) => eprintln!("计算文件 {} 哈希时出错: {}", path.display(), e),
         }
         pb.inc(1);
     });
 
-    pb.finish_with_message(format!("{} 完成", dir.display().to_string().green()));
-    
+    pb.finish_with_message("完整哈希计算完成".to_string());
+
     let result_map = Arc::try_unwrap(map)
         .unwrap()
         .into_inner()
         .unwrap();
-    
+
     Ok(result_map)
 }
 
-fn compare_folders(dir_a: &Path, dir_b: &Path) -> Result<(Vec<PathBuf>, Vec<PathBuf>)> {
-    let a_map = get_md5_dict(dir_a)?;
+fn compare_folders(
+    dir_a: &Path,
+    dir_b: &Path,
+    hash_type: HashType,
+    max_read_bytes: Option<u64>,
+    filter: &FileFilter,
+    cache: Option<&Mutex<HashCache>>,
+) -> Result<(Vec<DuplicateGroup>, Vec<(PathBuf, Option<String>)>, FilterStats)> {
+    let (paths_a, stats_a) = collect_files(dir_a, filter);
+    let (paths_b, stats_b) = collect_files(dir_b, filter);
+    let mut filter_stats = FilterStats::default();
+    filter_stats.merge(&stats_a);
+    filter_stats.merge(&stats_b);
+    println!("{}", format!("A 中 {} 个文件，B 中 {} 个文件", paths_a.len(), paths_b.len()).cyan());
+    if filter_stats.total() > 0 {
+        println!(
+            "{}",
+            format!(
+                "过滤阶段跳过 {} 个文件（扩展名 {}，体积 {}，路径 {}）",
+                filter_stats.total(),
+                filter_stats.skipped_ext,
+                filter_stats.skipped_size,
+                filter_stats.skipped_path
+            )
+            .yellow()
+        );
+    }
+
+    // 第一阶段：按大小分组，跨 A/B 合并后只有一个候选的分组必然唯一，无需再读内容。
+    // 来源（A 还是 B）在这里打标签随路径一起传递，而不是之后再用 starts_with
+    // 去猜——当 B 嵌套在 A 内部，或两个目录相同时，前缀判断会把文件分错组。
+    let mut all_paths: Vec<(PathBuf, Origin)> = Vec::with_capacity(paths_a.len() + paths_b.len());
+    all_paths.extend(paths_a.iter().cloned().map(|p| (p, Origin::A)));
+    all_paths.extend(paths_b.iter().cloned().map(|p| (p, Origin::B)));
+    let size_groups = group_by_size(&all_paths);
+    let (size_candidates, size_unique) = candidates_from_groups(size_groups);
+    println!("{}", format!("按大小排除 {} 个唯一文件", size_unique.len()).green());
+
+    // 第二阶段：对剩余候选只读取文件开头做分块哈希，再次淘汰明显不同的文件。
+    let partial_groups = group_by_partial_hash(&size_candidates, hash_type, max_read_bytes);
+    let (hash_candidates, partial_unique) = candidates_from_groups(partial_groups);
+    println!("{}", format!("按分块哈希排除 {} 个唯一文件", partial_unique.len()).green());
+
+    let candidates_a: Vec<PathBuf> = hash_candidates
+        .iter()
+        .filter(|(_, origin)| *origin == Origin::A)
+        .map(|(p, _)| p.clone())
+        .collect();
+    let candidates_b: Vec<PathBuf> = hash_candidates
+        .iter()
+        .filter(|(_, origin)| *origin == Origin::B)
+        .map(|(p, _)| p.clone())
+        .collect();
+
+    // 第三阶段：只对仍然可能重复的文件计算完整内容哈希。
+    let a_map = get_hash_dict(&candidates_a, hash_type, max_read_bytes, cache)?;
     println!("{}", "文件夹 A 计算完毕".green());
-    let b_map = get_md5_dict(dir_b)?;
+    let b_map = get_hash_dict(&candidates_b, hash_type, max_read_bytes, cache)?;
     println!("{}", "文件夹 B 计算完毕".green());
 
-    let mut b_duplicates = Vec::new();
+    let mut duplicate_groups = Vec::new();
     let mut b_unique = Vec::new();
 
     // A 中的文件
-    for (md5, a_paths) in &a_map {
-        if let Some(b_paths) = b_map.get(md5) {
-            b_duplicates.extend(b_paths.clone());
+    for (digest, a_paths) in &a_map {
+        if let Some(b_paths) = b_map.get(digest) {
+            let mut files = Vec::new();
+            let tagged = a_paths
+                .iter()
+                .map(|p| (p, Origin::A))
+                .chain(b_paths.iter().map(|p| (p, Origin::B)));
+            for (p, origin) in tagged {
+                match fs::metadata(p) {
+                    Ok(metadata) => match metadata.modified() {
+                        Ok(modified) => files.push(DupeFile {
+                            path: p.clone(),
+                            modified,
+                            inode: file_inode(&metadata),
+                            origin,
+                        }),
+                        Err(e) => eprintln!("读取 {} 修改时间时出错: {}", p.display(), e),
+                    },
+                    Err(e) => eprintln!("读取 {} 元数据时出错: {}", p.display(), e),
+                }
+            }
+            duplicate_groups.push(DuplicateGroup {
+                hash: digest.clone(),
+                files,
+            });
         } else {
-            println!("{} {}", format!("在 A 独有 (MD5 = {})", md5).red(), "");
+            println!("{} {}", format!("在 A 独有 (哈希 = {})", digest).red(), "");
             for p in a_paths {
                 println!("  {}", p.display());
             }
         }
     }
 
-    // B 中独有
-    for (md5, b_paths) in &b_map {
-        if !a_map.contains_key(md5) {
-            println!("{} {}", format!("在 B 独有 (MD5 = {})", md5).blue(), "");
+    // B 中独有：这一步已经算过完整哈希，顺带记下来，报告里就不用留空了。
+    for (digest, b_paths) in &b_map {
+        if !a_map.contains_key(digest) {
+            println!("{} {}", format!("在 B 独有 (哈希 = {})", digest).blue(), "");
             for p in b_paths {
                 println!("  {}", p.display());
-                b_unique.push(p.clone());
+                b_unique.push((p.clone(), Some(digest.clone())));
             }
         }
     }
-    
-    Ok((b_duplicates, b_unique))
-}
 
-fn main() -> Result<()> {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 3 {
-        eprintln!("{}",
-            "用法: chart_compare <源目录> <目标目录> [操作]\n可选操作:\n  [y] 删除重复\n  [o] 输出重复\n  [u] 输出独有".red()
+    // 前两个阶段已经确定唯一的文件，同样计入 B 独有列表；这些文件没有算过
+    // 完整哈希（按大小/分块哈希就已经排除，没必要再读内容），哈希留空。
+    for (p, origin) in size_unique.iter().chain(partial_unique.iter()) {
+        if *origin == Origin::B {
+            b_unique.push((p.clone(), None));
+        }
+    }
+
+    let already_hardlinked: usize = duplicate_groups
+        .iter()
+        .map(|g| g.already_hardlinked_count())
+        .sum();
+    if already_hardlinked > 0 {
+        println!(
+            "{}",
+            format!(
+                "{} 个重复文件已经与组内其他文件共享同一 inode（已是硬链接），不算作可回收空间",
+                already_hardlinked
+            )
+            .yellow()
         );
-        exit(1);
     }
-    let dir_a = Path::new(&args[1]);
-    let dir_b = Path::new(&args[2]);
-    let input = args.get(3);
 
-    let (b_duplicates, b_unique) = compare_folders(dir_a, dir_b)?;
+    Ok((duplicate_groups, b_unique, filter_stats))
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let dir_a = cli.dir_a.as_path();
+    let dir_b = cli.dir_b.as_path();
+    let input = cli.action.as_ref();
+
+    let filter = FileFilter::new(
+        cli.ext.as_deref(),
+        cli.exclude_ext.as_deref(),
+        cli.min_size,
+        cli.exclude_path.as_deref(),
+    )?;
+    let cache = if cli.no_cache {
+        None
+    } else {
+        Some(Mutex::new(HashCache::load(cli.cache_file.clone())?))
+    };
+    let (duplicate_groups, b_unique, filter_stats) = compare_folders(
+        dir_a,
+        dir_b,
+        cli.hash,
+        cli.max_read_bytes,
+        &filter,
+        cache.as_ref(),
+    )?;
+    if let Some(cache) = &cache {
+        cache.lock().unwrap().save()?;
+    }
+    let b_duplicates: Vec<PathBuf> = duplicate_groups
+        .iter()
+        .flat_map(|g| g.files.iter())
+        .filter(|f| f.origin == Origin::B)
+        .map(|f| f.path.clone())
+        .collect();
     println!("{}", format!("共找到 {} 个重复文件", b_duplicates.len()).cyan());
     println!("{}", format!("共找到 {} 个 B 中独有文件", b_unique.len()).cyan());
+    println!(
+        "{}",
+        format!(
+            "过滤跳过 {} 个文件（扩展名 {}，体积 {}，路径 {}）",
+            filter_stats.total(),
+            filter_stats.skipped_ext,
+            filter_stats.skipped_size,
+            filter_stats.skipped_path
+        )
+        .cyan()
+    );
+
+    if cli.output.is_some() || cli.format != ReportFormat::Text {
+        let report = report::build_report(&duplicate_groups, &b_unique);
+        report::write_report(&report, cli.format, cli.output.as_deref())?;
+    }
 
     let input = if let Some(input) = input {
         input.clone()
     } else {
         println!("{}", format!(
-            "比较完成，请选择操作 ({})\n  [y] 删除 B 文件夹中重复文件\n  [o] 输出重复文件列表到 BSame_files.txt\n  [u] 输出 B 独有文件列表到 BUnique_files.txt: ",
+            "比较完成，请选择操作 ({})\n  [y] 按 --delete-method 删除重复文件\n  [o] 输出重复文件列表到 BSame_files.txt\n  [u] 输出 B 独有文件列表到 BUnique_files.txt\n  [l] 将 B 中重复文件替换为硬链接\n  [s] 将 B 中重复文件替换为符号链接: ",
             dir_b.display()).yellow()
         );
         let mut user_input = String::new();
@@ -130,19 +502,28 @@ fn main() -> Result<()> {
     };
 
     if input.contains("y") {
-        for file in &b_duplicates {
-            if fs::remove_file(&file).is_ok() {
-                println!("{}", format!("已删除 {}", file.display()).green());
-            } else {
-                println!("{}", format!("删除失败 {}", file.display()).red());
+        if cli.delete_method == DeleteMethod::None {
+            println!(
+                "{}",
+                "未指定 --delete-method，不知道该保留哪一份，不会删除任何文件".yellow()
+            );
+        } else {
+            for group in &duplicate_groups {
+                for file in group.files_to_delete(cli.delete_method) {
+                    if fs::remove_file(&file).is_ok() {
+                        println!("{}", format!("已删除 {}", file.display()).green());
+                    } else {
+                        println!("{}", format!("删除失败 {}", file.display()).red());
+                    }
+                }
             }
+            println!("{}", "删除任务完成".green());
         }
-        println!("{}", "删除任务完成".green());
     }
     if input.contains("o") {
         let mut output_file = File::create("BSame_files.txt")
             .with_context(|| format!("无法创建 BSame_files.txt"))?;
-        
+
         for file in &b_duplicates {
             writeln!(output_file, "{}", file.display())
                 .with_context(|| format!("无法写入: {}", file.display()))?;
@@ -152,12 +533,51 @@ fn main() -> Result<()> {
     if input.contains("u") {
         let mut output_file = File::create("BUnique_files.txt")
             .with_context(|| format!("无法创建 BUnique_files.txt"))?;
-        
-        for file in &b_unique {
-            writeln!(output_file, "{}", file.display())
-                .with_context(|| format!("无法写入: {}", file.display()))?;
+
+        for (path, _hash) in &b_unique {
+            writeln!(output_file, "{}", path.display())
+                .with_context(|| format!("无法写入: {}", path.display()))?;
         }
         println!("{}", format!("B 中独有文件列表已输出到 BUnique_files.txt").green());
     }
+    if input.contains("l") || input.contains("s") {
+        let kind = if input.contains("l") {
+            LinkKind::Hard
+        } else {
+            LinkKind::Symbolic
+        };
+        for group in &duplicate_groups {
+            let Some((keep, removed)) = group.survivor_and_victims_for_link(cli.delete_method) else {
+                continue;
+            };
+            let keep_inode = group
+                .files
+                .iter()
+                .find(|f| f.path == keep)
+                .and_then(|f| f.inode);
+            for victim in removed {
+                let victim_file = group.files.iter().find(|f| f.path == victim);
+                if victim_file.map_or(true, |f| f.origin != Origin::B) {
+                    continue;
+                }
+                let victim_inode = victim_file.and_then(|f| f.inode);
+                if victim_inode.is_some() && victim_inode == keep_inode {
+                    continue;
+                }
+                match link::replace_with_link(&victim, &keep, kind) {
+                    Ok(()) => println!(
+                        "{}",
+                        format!("已将 {} 替换为链接 -> {}", victim.display(), keep.display())
+                            .green()
+                    ),
+                    Err(e) => println!(
+                        "{}",
+                        format!("替换链接失败 {}: {}", victim.display(), e).red()
+                    ),
+                }
+            }
+        }
+        println!("{}", "链接替换任务完成".green());
+    }
     Ok(())
 }
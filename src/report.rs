@@ -0,0 +1,191 @@
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::delete::DuplicateGroup;
+
+/// 报告输出格式：`text` 对应现有的人类可读摘要，`json`/`csv` 供下游脚本解析。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    Json,
+    Csv,
+    Text,
+}
+
+impl Default for ReportFormat {
+    fn default() -> Self {
+        ReportFormat::Text
+    }
+}
+
+#[derive(Serialize)]
+pub struct FileEntry {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: SystemTime,
+    /// 只有在比较流程里实际算过完整哈希时才有值；按大小/分块哈希提前判定为
+    /// 唯一的文件不会被回头补算完整哈希，这里如实留空。
+    pub hash: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct DuplicateGroupReport {
+    pub hash: String,
+    pub files: Vec<FileEntry>,
+}
+
+#[derive(Serialize)]
+pub struct Summary {
+    pub duplicate_groups: usize,
+    pub duplicate_files: usize,
+    pub unique_files: usize,
+    pub reclaimable_bytes: u64,
+}
+
+#[derive(Serialize)]
+pub struct Report {
+    pub duplicates: Vec<DuplicateGroupReport>,
+    pub unique: Vec<FileEntry>,
+    pub summary: Summary,
+}
+
+fn file_entry(path: &Path, hash: Option<String>) -> Option<FileEntry> {
+    let metadata = fs::metadata(path).ok()?;
+    let modified = metadata.modified().ok()?;
+    Some(FileEntry {
+        path: path.to_path_buf(),
+        size: metadata.len(),
+        modified,
+        hash,
+    })
+}
+
+pub fn build_report(
+    duplicate_groups: &[DuplicateGroup],
+    unique_files: &[(PathBuf, Option<String>)],
+) -> Report {
+    let mut duplicates = Vec::new();
+    let mut duplicate_files = 0;
+    let mut reclaimable_bytes: u64 = 0;
+
+    for group in duplicate_groups {
+        let files: Vec<FileEntry> = group
+            .files
+            .iter()
+            .filter_map(|f| file_entry(&f.path, Some(group.hash.clone())))
+            .collect();
+        duplicate_files += files.len();
+        if let Some(first) = files.first() {
+            let extra_copies = files
+                .len()
+                .saturating_sub(1)
+                .saturating_sub(group.already_hardlinked_count());
+            reclaimable_bytes += first.size * extra_copies as u64;
+        }
+        duplicates.push(DuplicateGroupReport {
+            hash: group.hash.clone(),
+            files,
+        });
+    }
+
+    let unique: Vec<FileEntry> = unique_files
+        .iter()
+        .filter_map(|(p, hash)| file_entry(p, hash.clone()))
+        .collect();
+
+    let summary = Summary {
+        duplicate_groups: duplicates.len(),
+        duplicate_files,
+        unique_files: unique.len(),
+        reclaimable_bytes,
+    };
+
+    Report {
+        duplicates,
+        unique,
+        summary,
+    }
+}
+
+fn format_modified(modified: SystemTime) -> String {
+    match modified.duration_since(std::time::UNIX_EPOCH) {
+        Ok(d) => d.as_secs().to_string(),
+        Err(_) => "0".to_string(),
+    }
+}
+
+fn to_csv(report: &Report) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(["kind", "hash", "path", "size", "modified"])?;
+
+    for group in &report.duplicates {
+        for file in &group.files {
+            writer.write_record([
+                "duplicate".to_string(),
+                group.hash.clone(),
+                file.path.display().to_string(),
+                file.size.to_string(),
+                format_modified(file.modified),
+            ])?;
+        }
+    }
+    for file in &report.unique {
+        writer.write_record([
+            "unique".to_string(),
+            file.hash.clone().unwrap_or_default(),
+            file.path.display().to_string(),
+            file.size.to_string(),
+            format_modified(file.modified),
+        ])?;
+    }
+
+    let data = writer
+        .into_inner()
+        .context("无法生成 CSV 报告")?;
+    String::from_utf8(data).context("CSV 报告包含非 UTF-8 数据")
+}
+
+fn to_text(report: &Report) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "重复分组: {}，重复文件: {}，独有文件: {}，可回收空间: {} 字节\n",
+        report.summary.duplicate_groups,
+        report.summary.duplicate_files,
+        report.summary.unique_files,
+        report.summary.reclaimable_bytes
+    ));
+    for group in &report.duplicates {
+        out.push_str(&format!("[重复] 哈希 = {}\n", group.hash));
+        for file in &group.files {
+            out.push_str(&format!("  {}\n", file.path.display()));
+        }
+    }
+    out.push_str("[独有]\n");
+    for file in &report.unique {
+        out.push_str(&format!("  {}\n", file.path.display()));
+    }
+    out
+}
+
+pub fn write_report(report: &Report, format: ReportFormat, output: Option<&Path>) -> Result<()> {
+    let body = match format {
+        ReportFormat::Json => {
+            serde_json::to_string_pretty(report).context("无法序列化 JSON 报告")?
+        }
+        ReportFormat::Csv => to_csv(report)?,
+        ReportFormat::Text => to_text(report),
+    };
+
+    match output {
+        Some(path) => {
+            fs::write(path, body).with_context(|| format!("无法写入报告文件: {}", path.display()))
+        }
+        None => {
+            println!("{}", body);
+            Ok(())
+        }
+    }
+}